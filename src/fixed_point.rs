@@ -0,0 +1,108 @@
+//! Low-CU fixed-point accessors for programs that do on-chain math and want
+//! to avoid pulling in `rust_decimal::Decimal`. Gated behind the
+//! `fixed-point` feature so the `fixed` dependency stays optional.
+use fixed::types::I80F48;
+
+use crate::{CurrentResult, PullFeedAccountData, PRECISION};
+
+/// Powers of ten up to `PRECISION`, indexed by exponent. Scaling a raw
+/// `i128` by an arbitrary decimal exponent is then a table lookup rather
+/// than a `pow` call inside a Solana program.
+pub const DECIMAL_CONSTANTS: [i128; PRECISION as usize + 1] = {
+    let mut table = [1i128; PRECISION as usize + 1];
+    let mut i = 1;
+    while i <= PRECISION as usize {
+        table[i] = table[i - 1] * 10;
+        i += 1;
+    }
+    table
+};
+
+/// Converts a raw `i128` value scaled by `10^PRECISION` into an `I80F48`,
+/// instead of routing through `Decimal::from_i128_with_scale`. Split into an
+/// integer-part shift and a fractional-part multiply/shift rather than doing
+/// `raw * (1 << 48) / 10^PRECISION` directly, since that product overflows
+/// `i128` for realistically large raw values (e.g. high-priced feeds); the
+/// fractional remainder is always smaller than `10^PRECISION`, so its shift
+/// can't overflow.
+pub fn to_i80f48(raw: i128) -> I80F48 {
+    let scale = DECIMAL_CONSTANTS[PRECISION as usize];
+    let integer_part = (raw / scale) << 48;
+    let fractional_part = ((raw % scale) << 48) / scale;
+    I80F48::from_bits(integer_part + fractional_part)
+}
+
+impl CurrentResult {
+    /// The median value of the submissions needed for quorom size, as a
+    /// no-allocation `I80F48` rather than a `Decimal`.
+    pub fn value_fixed(&self) -> Option<I80F48> {
+        if self.slot == 0 {
+            return None;
+        }
+        Some(to_i80f48(self.value))
+    }
+
+    /// The standard deviation of the submissions needed for quorom size, as
+    /// an `I80F48`.
+    pub fn std_dev_fixed(&self) -> Option<I80F48> {
+        if self.slot == 0 {
+            return None;
+        }
+        Some(to_i80f48(self.std_dev))
+    }
+
+    /// The mean of the submissions needed for quorom size, as an `I80F48`.
+    pub fn mean_fixed(&self) -> Option<I80F48> {
+        if self.slot == 0 {
+            return None;
+        }
+        Some(to_i80f48(self.mean))
+    }
+}
+
+impl PullFeedAccountData {
+    /// The median value of the submissions needed for quorom size, as an
+    /// `I80F48`. See `CurrentResult::value_fixed`.
+    pub fn value_fixed(&self) -> Option<I80F48> {
+        self.result.value_fixed()
+    }
+
+    /// The standard deviation of the submissions needed for quorom size, as
+    /// an `I80F48`. See `CurrentResult::std_dev_fixed`.
+    pub fn std_dev_fixed(&self) -> Option<I80F48> {
+        self.result.std_dev_fixed()
+    }
+
+    /// The mean of the submissions needed for quorom size, as an `I80F48`.
+    /// See `CurrentResult::mean_fixed`.
+    pub fn mean_fixed(&self) -> Option<I80F48> {
+        self.result.mean_fixed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_i80f48_converts_one_unit() {
+        let raw = 10i128.pow(PRECISION);
+        assert_eq!(to_i80f48(raw), I80F48::from_num(1));
+    }
+
+    #[test]
+    fn to_i80f48_preserves_fractional_part() {
+        let raw = 25 * 10i128.pow(PRECISION - 2); // 0.25
+        let result = to_i80f48(raw);
+        assert!((result - I80F48::from_num(0.25)).abs() < I80F48::from_num(0.0001));
+    }
+
+    #[test]
+    fn to_i80f48_does_not_overflow_for_high_priced_feeds() {
+        // A $1,000,000 price at PRECISION = 18 would overflow a naive
+        // `raw * (1 << 48) / 10^PRECISION` computed entirely in i128.
+        let raw = 1_000_000 * 10i128.pow(PRECISION);
+        let result = to_i80f48(raw);
+        assert_eq!(result, I80F48::from_num(1_000_000));
+    }
+}
@@ -4,8 +4,37 @@ use solana_program::clock::Clock;
 use std::cell::Ref;
 use bytemuck;
 
+#[cfg(feature = "fixed-point")]
+pub mod fixed_point;
+
 pub const PRECISION: u32 = 18;
 
+/// Fallback for `PullFeedAccountData::get_value_from_config` when the
+/// account's `max_staleness` is unset (zero).
+pub const DEFAULT_MAX_STALENESS_SLOTS: u64 = 150;
+/// Fallback for `PullFeedAccountData::get_value_from_config` when the
+/// account's `min_sample_size` is unset (zero).
+pub const DEFAULT_MIN_SAMPLE_SIZE: u32 = 1;
+/// Approximate Solana slot duration in milliseconds (~400ms/slot), used to
+/// translate a slot-denominated staleness bound into a wall-clock bound for
+/// `last_update_timestamp`.
+pub const DEFAULT_SLOT_DURATION_MILLIS: i64 = 400;
+
+/// The result of a self-configuring feed read via `get_value_from_config`,
+/// carrying the provenance a caller needs for logging or risk decisions
+/// alongside the value itself.
+#[derive(Clone, Copy, Debug)]
+pub struct FeedRead {
+    /// The aggregated value of the read.
+    pub value: Decimal,
+    /// The most recent slot among the submissions used for the read.
+    pub slot: u64,
+    /// How many slots old the most recent submission used was, relative to `clock.slot`.
+    pub staleness_slots: u64,
+    /// How many submissions were used to compute `value`.
+    pub samples_used: u32,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct CurrentResult {
@@ -201,6 +230,7 @@ impl PullFeedAccountData {
     /// * `clock` - the clock to use for the current slot
     /// * `max_staleness` - the maximum number of slots to consider
     /// * `min_samples` - the minimum number of samples required to return a value
+    ///
     /// **returns**
     /// * `Ok(Decimal)` - the median value of the submissions in the last `max_staleness` slots
     pub fn get_value(
@@ -229,6 +259,179 @@ impl PullFeedAccountData {
         Ok(Decimal::from_i128_with_scale(median, PRECISION))
     }
 
+    /// **method**
+    /// get_value_aggregated
+    /// Like `get_value`, but lets the caller pick the aggregation strategy
+    /// used to collapse the filtered submission set into a single value,
+    /// rather than always taking the lower-bound median. `AggregationMode::LowerMedian`
+    /// reproduces `get_value`'s behavior exactly.
+    /// **arguments**
+    /// * `clock` - the clock to use for the current slot
+    /// * `max_staleness` - the maximum number of slots to consider
+    /// * `min_samples` - the minimum number of samples required to return a value
+    /// * `mode` - the aggregation strategy to apply to the surviving submissions
+    ///
+    /// **returns**
+    /// * `Ok(Decimal)` - the aggregated value of the submissions in the last `max_staleness` slots
+    pub fn get_value_aggregated(
+        &self,
+        clock: &Clock,
+        max_staleness: u64,
+        min_samples: u32,
+        only_positive: bool,
+        mode: AggregationMode,
+    ) -> Result<Decimal, OnDemandError> {
+        let submissions = self
+            .submissions
+            .iter()
+            .take_while(|s| !s.is_empty())
+            .filter(|s| s.slot > clock.slot.saturating_sub(max_staleness))
+            .collect::<Vec<_>>();
+        if submissions.len() < min_samples as usize {
+            return Err(OnDemandError::NotEnoughSamples);
+        }
+        let mut values = submissions.iter().map(|s| s.value).collect::<Vec<_>>();
+        let aggregated = aggregate(&mut values, mode).ok_or(OnDemandError::NotEnoughSamples)?;
+        if only_positive && aggregated <= 0 {
+            return Err(OnDemandError::IllegalFeedValue);
+        }
+
+        Ok(Decimal::from_i128_with_scale(aggregated, PRECISION))
+    }
+
+    /// **method**
+    /// get_value_checked
+    /// Like `get_value`, but additionally rejects the read if the submissions'
+    /// relative deviation is too wide, or if too few submissions survived the
+    /// staleness filter. The deviation `conf = std_dev / |mean|` is computed
+    /// over the same filtered, non-stale submission set used for the median
+    /// (not the precomputed `CurrentResult`, which can reflect a different
+    /// slot), so the returned value is guaranteed to be both fresh and tight.
+    /// The variance is accumulated in `f64` rather than `i128`: squaring raw
+    /// `i128` prices at `PRECISION = 18` overflows `i128` for high-value
+    /// feeds, and an `i128` computation that merely saturates on overflow
+    /// would be fail-open (a saturated `conf` can still slip under a loose
+    /// `max_variance`).
+    /// **arguments**
+    /// * `clock` - the clock to use for the current slot
+    /// * `max_staleness` - the maximum number of slots to consider
+    /// * `min_samples` - the minimum number of samples required to return a value
+    ///
+    /// **returns**
+    /// * `Ok(Decimal)` - the median value, if fresh enough and tight enough
+    pub fn get_value_checked(
+        &self,
+        clock: &Clock,
+        max_staleness: u64,
+        min_samples: u32,
+    ) -> Result<Decimal, OnDemandError> {
+        let submissions = self
+            .submissions
+            .iter()
+            .take_while(|s| !s.is_empty())
+            .filter(|s| s.slot > clock.slot.saturating_sub(max_staleness))
+            .collect::<Vec<_>>();
+        if submissions.len() < min_samples as usize || submissions.len() < self.min_responses as usize {
+            return Err(OnDemandError::NotEnoughSamples);
+        }
+
+        let values = submissions.iter().map(|s| s.value).collect::<Vec<_>>();
+        let median = lower_bound_median(&mut values.clone()).ok_or(OnDemandError::NotEnoughSamples)?;
+
+        let n = values.len() as i128;
+        let sum: i128 = values.iter().sum();
+        let mean = sum / n;
+        let mean_f64 = mean as f64;
+        let variance_f64 = values
+            .iter()
+            .map(|v| {
+                let diff = (*v - mean) as f64;
+                diff * diff
+            })
+            .sum::<f64>()
+            / n as f64;
+        let std_dev_f64 = variance_f64.sqrt();
+        let conf_f64 = (std_dev_f64 / mean_f64.abs()) * 10f64.powi(PRECISION as i32);
+        // Fail closed: a non-finite ratio (e.g. from a zero mean) must not
+        // be silently treated as passing the gate.
+        if !conf_f64.is_finite() || conf_f64 > self.max_variance as f64 {
+            return Err(OnDemandError::ExcessiveConfidenceInterval);
+        }
+
+        Ok(Decimal::from_i128_with_scale(median, PRECISION))
+    }
+
+    /// **method**
+    /// get_value_from_config
+    /// Like `get_value`, but reads `max_staleness` and `min_sample_size` from
+    /// the account itself instead of the caller, and additionally checks
+    /// `last_update_timestamp` against a wall-clock bound derived from
+    /// `clock`. A `max_staleness` or `min_sample_size` of zero falls back to
+    /// `DEFAULT_MAX_STALENESS_SLOTS` / `DEFAULT_MIN_SAMPLE_SIZE` rather than
+    /// accepting every submission or requiring none. Returns a `FeedRead`
+    /// rather than a bare `Decimal` so callers get the staleness and sample
+    /// count used for the read alongside the value, instead of re-deriving
+    /// them from hardcoded arguments that can drift from the feed's
+    /// configured policy. Returns `OnDemandError::StalePrice` (distinct from
+    /// `NotEnoughSamples`) when there are enough samples but the account's
+    /// `last_update_timestamp` is older than the wall-clock bound, so callers
+    /// can tell "too few oracles" apart from "data is stale" for risk logging.
+    /// **arguments**
+    /// * `clock` - the clock to use for the current slot and wall-clock time
+    /// * `only_positive` - reject the read if the aggregated value is not positive
+    ///
+    /// **returns**
+    /// * `Ok(FeedRead)` - the value, slot, staleness, and sample count used for the read
+    pub fn get_value_from_config(
+        &self,
+        clock: &Clock,
+        only_positive: bool,
+    ) -> Result<FeedRead, OnDemandError> {
+        let max_staleness = if self.max_staleness == 0 {
+            DEFAULT_MAX_STALENESS_SLOTS
+        } else {
+            self.max_staleness as u64
+        };
+        let min_samples = if self.min_sample_size == 0 {
+            DEFAULT_MIN_SAMPLE_SIZE
+        } else {
+            self.min_sample_size as u32
+        };
+
+        let submissions = self
+            .submissions
+            .iter()
+            .take_while(|s| !s.is_empty())
+            .filter(|s| s.slot > clock.slot.saturating_sub(max_staleness))
+            .collect::<Vec<_>>();
+        if submissions.len() < min_samples as usize {
+            return Err(OnDemandError::NotEnoughSamples);
+        }
+
+        let max_slot = submissions.iter().map(|s| s.slot).max().unwrap_or(0);
+        let staleness_slots = clock.slot.saturating_sub(max_slot);
+
+        let timestamp_staleness_millis =
+            clock.unix_timestamp.saturating_sub(self.last_update_timestamp).saturating_mul(1000);
+        if timestamp_staleness_millis > (max_staleness as i64).saturating_mul(DEFAULT_SLOT_DURATION_MILLIS) {
+            return Err(OnDemandError::StalePrice);
+        }
+
+        let median =
+            lower_bound_median(&mut submissions.iter().map(|s| s.value).collect::<Vec<_>>())
+                .ok_or(OnDemandError::NotEnoughSamples)?;
+        if only_positive && median <= 0 {
+            return Err(OnDemandError::IllegalFeedValue);
+        }
+
+        Ok(FeedRead {
+            value: Decimal::from_i128_with_scale(median, PRECISION),
+            slot: max_slot,
+            staleness_slots,
+            samples_used: submissions.len() as u32,
+        })
+    }
+
     /// The median value of the submissions needed for quorom size
     pub fn value(&self) -> Option<Decimal> {
         self.result.value()
@@ -258,6 +461,65 @@ impl PullFeedAccountData {
     pub fn max_value(&self) -> Option<Decimal> {
         self.result.max_value()
     }
+
+    /// **method**
+    /// twap
+    /// Computes a slot-weighted average of the `historical_results` ring,
+    /// walking backwards from `historical_result_idx` and weighting each
+    /// sample's `mean` by the number of slots it was in effect for, i.e. the
+    /// slot gap to the next (more recent) sample. Samples older than
+    /// `lookback_slots` relative to `clock.slot` are excluded. `CompactResult::mean`
+    /// is an `f32`, so the weighted sum is accumulated in `f64` to avoid
+    /// compounding rounding error across the ring before converting back to
+    /// `Decimal` at the end.
+    /// **arguments**
+    /// * `clock` - the clock to use for the current slot
+    /// * `lookback_slots` - the maximum age, in slots, of samples to include
+    ///
+    /// **returns**
+    /// * `Ok(Decimal)` - the slot-weighted average of the in-window samples
+    pub fn twap(&self, clock: &Clock, lookback_slots: u64) -> Result<Decimal, OnDemandError> {
+        let len = self.historical_results.len();
+        let start = self.historical_result_idx as usize % len;
+
+        // Walk the ring backwards from the most recent entry, oldest-first
+        // ordering within the window so consecutive slot gaps can be paired.
+        let mut in_window = Vec::with_capacity(len);
+        for i in 0..len {
+            let idx = (start + len - i) % len;
+            let sample = &self.historical_results[idx];
+            if sample.slot == 0 {
+                continue;
+            }
+            if clock.slot.saturating_sub(sample.slot) > lookback_slots {
+                break;
+            }
+            in_window.push(*sample);
+        }
+        in_window.reverse();
+
+        if in_window.len() < 2 {
+            return Err(OnDemandError::NotEnoughSamples);
+        }
+
+        let mut weighted_sum: f64 = 0.0;
+        let mut total_slots: f64 = 0.0;
+        for pair in in_window.windows(2) {
+            let (cur, next) = (pair[0], pair[1]);
+            let weight = next.slot.saturating_sub(cur.slot) as f64;
+            weighted_sum += cur.mean as f64 * weight;
+            total_slots += weight;
+        }
+
+        if total_slots == 0.0 {
+            return Err(OnDemandError::NotEnoughSamples);
+        }
+
+        let twap = weighted_sum / total_slots;
+        Decimal::from_f64_retain(twap)
+            .map(|d| d.round_dp(PRECISION))
+            .ok_or(OnDemandError::DecimalConversionError)
+    }
 }
 
 // takes the rounded down median of a list of numbers
@@ -271,6 +533,58 @@ pub fn lower_bound_median(numbers: &mut Vec<i128>) -> Option<i128> {
     Some(numbers[len / 2])
 }
 
+/// Aggregation strategy used to collapse a set of oracle submissions into a
+/// single value. `LowerMedian` is the crate's historical behavior and is the
+/// default `get_value` continues to use.
+#[derive(Clone, Copy, Debug)]
+pub enum AggregationMode {
+    /// The rounded-down median, as computed by `lower_bound_median`.
+    LowerMedian,
+    /// Sort the samples, drop `floor(trim_pct * n)` from each end, and
+    /// return the integer mean of what remains.
+    TrimmedMean { trim_pct: f64 },
+    /// Discard samples whose absolute deviation from the median exceeds
+    /// `k` times the median absolute deviation (MAD), then return the
+    /// median of the survivors. `MAD == 0` keeps all identical values.
+    MadFiltered { k: f64 },
+}
+
+/// Collapses `numbers` into a single value using the given `AggregationMode`.
+/// Returns `None` if `numbers` is empty or every sample is filtered out.
+pub fn aggregate(numbers: &mut Vec<i128>, mode: AggregationMode) -> Option<i128> {
+    match mode {
+        AggregationMode::LowerMedian => lower_bound_median(numbers),
+        AggregationMode::TrimmedMean { trim_pct } => {
+            numbers.sort();
+            let len = numbers.len();
+            if len == 0 {
+                return None;
+            }
+            let trim = ((trim_pct * len as f64).floor() as usize).min(len / 2);
+            let survivors = &numbers[trim..len - trim];
+            if survivors.is_empty() {
+                return None;
+            }
+            Some(survivors.iter().sum::<i128>() / survivors.len() as i128)
+        }
+        AggregationMode::MadFiltered { k } => {
+            let median = lower_bound_median(numbers)?;
+            let mut deviations = numbers.iter().map(|x| (x - median).abs()).collect::<Vec<_>>();
+            let mad = lower_bound_median(&mut deviations)?;
+            let survivors = if mad == 0 {
+                numbers.clone()
+            } else {
+                numbers
+                    .iter()
+                    .copied()
+                    .filter(|x| (*x - median).abs() as f64 <= k * mad as f64)
+                    .collect::<Vec<_>>()
+            };
+            lower_bound_median(&mut survivors.clone())
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 #[repr(u32)]
 pub enum OnDemandError {
@@ -325,8 +639,244 @@ pub enum OnDemandError {
     AccountDeserializeError,
     NotEnoughSamples,
     IllegalFeedValue,
+    ExcessiveConfidenceInterval,
+    StalePrice,
     CustomMessage(String),
     SwitchboardRandomnessTooOld,
     AddressLookupTableFetchError,
     AddressLookupTableDeserializeError,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    fn clock(slot: u64, unix_timestamp: i64) -> Clock {
+        Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    fn submission(oracle_byte: u8, slot: u64, value: i128) -> OracleSubmission {
+        let mut s = OracleSubmission::zeroed();
+        s.oracle = Pubkey::new_from_array([oracle_byte; 32]);
+        s.slot = slot;
+        s.value = value;
+        s
+    }
+
+    fn feed_with_submissions(values: &[(u64, i128)]) -> PullFeedAccountData {
+        let mut feed = PullFeedAccountData::zeroed();
+        for (i, (slot, value)) in values.iter().enumerate() {
+            feed.submissions[i] = submission(i as u8 + 1, *slot, *value);
+        }
+        feed
+    }
+
+    #[test]
+    fn get_value_checked_does_not_overflow_for_high_priced_feeds() {
+        // BTC-scale raw values at PRECISION = 18 (~$100,000), ~0.01% spread.
+        let raw_base: i128 = 100_000 * 10i128.pow(PRECISION);
+        let spread = raw_base / 10_000;
+        let mut feed = feed_with_submissions(&[
+            (10, raw_base),
+            (10, raw_base + spread),
+            (10, raw_base - spread),
+        ]);
+        feed.min_responses = 3;
+        feed.max_variance = 10u64.pow(PRECISION) / 100; // 1% tolerance
+
+        let clock = clock(20, 0);
+        // Must not panic/overflow, and the tight spread should pass the gate.
+        assert!(feed.get_value_checked(&clock, 100, 3).is_ok());
+    }
+
+    #[test]
+    fn get_value_checked_rejects_wide_confidence_interval() {
+        let raw_base: i128 = 100_000 * 10i128.pow(PRECISION);
+        let spread = raw_base / 10; // 10% deviation, far over tolerance
+        let mut feed =
+            feed_with_submissions(&[(10, raw_base + spread), (10, raw_base - spread)]);
+        feed.min_responses = 2;
+        feed.max_variance = 10u64.pow(PRECISION) / 1000; // 0.1% tolerance
+
+        let clock = clock(20, 0);
+        let err = feed.get_value_checked(&clock, 100, 2).unwrap_err();
+        assert!(matches!(err, OnDemandError::ExcessiveConfidenceInterval));
+    }
+
+    #[test]
+    fn get_value_checked_rejects_instead_of_saturating_for_large_high_priced_spreads() {
+        // Regression test: a naive `i128::saturating_mul` scaling of
+        // std_dev*10^PRECISION saturates to i128::MAX for any std_dev beyond
+        // ~170 price units on a BTC-scale feed, which pins `conf` at a small
+        // fixed value and makes the gate fail-open. Two submissions 5% apart
+        // on a $100k-scale feed must still be rejected by a 1% tolerance.
+        let raw_base: i128 = 100_000 * 10i128.pow(PRECISION);
+        let spread = raw_base * 5 / 100; // 5% true dispersion
+        let mut feed =
+            feed_with_submissions(&[(10, raw_base + spread), (10, raw_base - spread)]);
+        feed.min_responses = 2;
+        feed.max_variance = 10u64.pow(PRECISION) / 100; // 1% tolerance
+
+        let clock = clock(20, 0);
+        let err = feed.get_value_checked(&clock, 100, 2).unwrap_err();
+        assert!(matches!(err, OnDemandError::ExcessiveConfidenceInterval));
+    }
+
+    #[test]
+    fn get_value_checked_uses_filtered_submissions_not_stale_result() {
+        // `self.result` is stale/mismatched, but the filtered submission set
+        // is tight; the gate must be computed from the latter.
+        let raw_base: i128 = 100_000 * 10i128.pow(PRECISION);
+        let spread = raw_base / 10_000; // 0.01% true dispersion
+        let mut feed =
+            feed_with_submissions(&[(10, raw_base + spread), (10, raw_base - spread)]);
+        feed.min_responses = 2;
+        feed.max_variance = 10u64.pow(PRECISION) / 100; // 1% tolerance
+        // A wildly different precomputed result that would fail the gate if
+        // it were consulted instead of the filtered submissions.
+        feed.result.mean = raw_base;
+        feed.result.std_dev = raw_base; // 100% deviation
+
+        let clock = clock(20, 0);
+        assert!(feed.get_value_checked(&clock, 100, 2).is_ok());
+    }
+
+    #[test]
+    fn get_value_checked_does_not_panic_when_clock_slot_is_small() {
+        let mut feed = feed_with_submissions(&[(1, 10), (1, 10)]);
+        feed.min_responses = 2;
+        let clock = clock(5, 0);
+        // max_staleness (100) exceeds clock.slot (5); must not underflow/panic.
+        let _ = feed.get_value_checked(&clock, 100, 2);
+    }
+
+    fn compact(mean: f32, slot: u64) -> CompactResult {
+        CompactResult {
+            std_dev: 0.0,
+            mean,
+            slot,
+        }
+    }
+
+    #[test]
+    fn twap_computes_slot_weighted_average_walking_the_ring_backwards() {
+        let mut feed = PullFeedAccountData::zeroed();
+        // Ring stored chronologically: idx0 oldest, idx2 most recent.
+        feed.historical_results[0] = compact(100.0, 10);
+        feed.historical_results[1] = compact(110.0, 20);
+        feed.historical_results[2] = compact(120.0, 25);
+        feed.historical_result_idx = 2;
+
+        let clock = clock(30, 0);
+        let result = feed.twap(&clock, 100).unwrap();
+
+        // weight(100) over [10, 20) = 10 slots, weight(110) over [20, 25) = 5 slots.
+        let expected = (100.0 * 10.0 + 110.0 * 5.0) / 15.0;
+        let expected = Decimal::from_f64_retain(expected).unwrap().round_dp(PRECISION);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn twap_errors_with_fewer_than_two_in_window_samples() {
+        let mut feed = PullFeedAccountData::zeroed();
+        feed.historical_results[0] = compact(100.0, 10);
+        feed.historical_result_idx = 0;
+
+        let clock = clock(10, 0);
+        let err = feed.twap(&clock, 100).unwrap_err();
+        assert!(matches!(err, OnDemandError::NotEnoughSamples));
+    }
+
+    #[test]
+    fn twap_excludes_samples_older_than_lookback() {
+        let mut feed = PullFeedAccountData::zeroed();
+        feed.historical_results[0] = compact(100.0, 10);
+        feed.historical_results[1] = compact(110.0, 20);
+        feed.historical_results[2] = compact(120.0, 25);
+        feed.historical_result_idx = 2;
+
+        // Only the last 4 slots are in window, which only covers one sample.
+        let clock = clock(29, 0);
+        let err = feed.twap(&clock, 4).unwrap_err();
+        assert!(matches!(err, OnDemandError::NotEnoughSamples));
+    }
+
+    #[test]
+    fn aggregate_trimmed_mean_drops_floor_trim_pct_from_each_end() {
+        let mut values = vec![1, 2, 3, 4, 5];
+        // trim = floor(0.2 * 5) = 1 from each end, leaving [2, 3, 4].
+        let result = aggregate(&mut values, AggregationMode::TrimmedMean { trim_pct: 0.2 }).unwrap();
+        assert_eq!(result, 3);
+    }
+
+    #[test]
+    fn aggregate_trimmed_mean_returns_none_when_trim_consumes_all_samples() {
+        let mut values = vec![1, 2, 3, 4];
+        // trim_pct = 0.9 is capped at len / 2, but that still empties the set here.
+        let result = aggregate(&mut values, AggregationMode::TrimmedMean { trim_pct: 0.9 });
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn aggregate_mad_filtered_keeps_all_identical_values_when_mad_is_zero() {
+        let mut values = vec![5, 5, 5, 5];
+        let result = aggregate(&mut values, AggregationMode::MadFiltered { k: 0.0 }).unwrap();
+        assert_eq!(result, 5);
+    }
+
+    #[test]
+    fn aggregate_mad_filtered_drops_outliers_beyond_k_times_mad() {
+        let mut values = vec![100, 101, 99, 102, 98, 1000];
+        let result = aggregate(&mut values, AggregationMode::MadFiltered { k: 3.0 }).unwrap();
+        assert!(result < 200, "outlier 1000 should have been filtered out, got {result}");
+    }
+
+    #[test]
+    fn get_value_aggregated_does_not_panic_when_clock_slot_is_small() {
+        let feed = feed_with_submissions(&[(1, 10), (1, 20), (1, 30)]);
+        let clock = clock(2, 0);
+        // max_staleness (100) exceeds clock.slot (2); must not underflow/panic.
+        let result = feed.get_value_aggregated(&clock, 100, 1, false, AggregationMode::LowerMedian);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn get_value_from_config_does_not_panic_when_clock_slot_is_small() {
+        let mut feed = feed_with_submissions(&[(1, 10), (1, 10)]);
+        feed.max_staleness = 1000; // exceeds clock.slot below
+        feed.min_sample_size = 1;
+        let clock = clock(5, 0);
+        let _ = feed.get_value_from_config(&clock, false);
+    }
+
+    #[test]
+    fn get_value_from_config_falls_back_to_defaults_when_fields_are_zero() {
+        let mut feed = feed_with_submissions(&[(9, 10), (9, 10)]);
+        feed.max_staleness = 0;
+        feed.min_sample_size = 0;
+        feed.last_update_timestamp = 100;
+        let clock = clock(10, 100);
+
+        let read = feed.get_value_from_config(&clock, false).unwrap();
+        assert_eq!(read.samples_used, 2);
+        assert_eq!(read.slot, 9);
+        assert_eq!(read.staleness_slots, 1);
+    }
+
+    #[test]
+    fn get_value_from_config_rejects_stale_timestamp_even_with_fresh_slots() {
+        let mut feed = feed_with_submissions(&[(9, 10), (9, 10)]);
+        feed.max_staleness = 10; // 10 slots * 400ms = 4s wall-clock bound
+        feed.min_sample_size = 1;
+        feed.last_update_timestamp = 0;
+        let clock = clock(10, 100); // 100s old, far beyond the 4s bound
+
+        let err = feed.get_value_from_config(&clock, false).unwrap_err();
+        assert!(matches!(err, OnDemandError::StalePrice));
+    }
 }
\ No newline at end of file